@@ -1,26 +1,35 @@
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
 use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+mod markdown;
+mod storage;
 
 #[derive(Serialize, Deserialize, Clone)]
 struct Note {
+    #[serde(default = "Uuid::new_v4")]
+    id: Uuid,
     title: String,
     content: String,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     tags: Vec<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
 }
 
 impl Note {
-    fn new(title: String, content: String, tags: Vec<String>) -> Note {
+    fn new(title: String, content: String, tags: Vec<String>, metadata: HashMap<String, String>) -> Note {
         let now = Utc::now();
         Note {
+            id: Uuid::new_v4(),
             title,
             content,
             created_at: now,
             updated_at: now,
             tags,
+            metadata,
         }
     }
 
@@ -30,28 +39,95 @@ impl Note {
     }
 }
 
+enum PasswordPromptMode {
+    Unlock,
+    CreatePassword { plaintext_notes: Vec<Note> },
+}
+
+struct PasswordInput {
+    mode: PasswordPromptMode,
+    password: String,
+    confirm_password: String,
+    error: Option<String>,
+}
+
 struct NotebookApp {
     notes: Vec<Note>,
+    password: Option<String>,
+    password_input: Option<PasswordInput>,
     new_title: String,
     new_content: String,
     new_tags: String,
-    edit_index: Option<usize>,
+    new_metadata: HashMap<String, String>,
+    metadata_key_buffer: String,
+    metadata_buffer: String,
+    edit_id: Option<Uuid>,
+    edit_buffers: HashMap<Uuid, String>,
+    search_query: String,
+    active_tag_filters: HashSet<String>,
+    tag_filter_mode: TagFilterMode,
+    save_error: Option<String>,
+    load_error: Option<String>,
+}
+
+#[derive(PartialEq)]
+enum TagFilterMode {
+    Any,
+    All,
+}
+
+fn trimmed_metadata_entry(key: &str, value: &str) -> Option<(String, String)> {
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key, value.trim().to_string()))
 }
 
 impl NotebookApp {
-    fn load_notes() -> Vec<Note> {
-        let path = "notes.json";
-        if Path::new(path).exists() {
-            let data = fs::read_to_string(path).expect("Konnte Datei nicht laden!");
-            serde_json::from_str(&data).expect("Konnte JSON nicht lesen!")
-        } else {
-            Vec::new()
+    fn new() -> NotebookApp {
+        let (mode, load_error) = match storage::inspect() {
+            storage::ExistingNotes::None => (PasswordPromptMode::CreatePassword { plaintext_notes: Vec::new() }, None),
+            storage::ExistingNotes::Plaintext(notes) => (PasswordPromptMode::CreatePassword { plaintext_notes: notes }, None),
+            storage::ExistingNotes::Encrypted => (PasswordPromptMode::Unlock, None),
+            storage::ExistingNotes::Error(err) => (PasswordPromptMode::Unlock, Some(err)),
+        };
+
+        NotebookApp {
+            notes: Vec::new(),
+            password: None,
+            password_input: if load_error.is_none() {
+                Some(PasswordInput {
+                    mode,
+                    password: String::new(),
+                    confirm_password: String::new(),
+                    error: None,
+                })
+            } else {
+                None
+            },
+            new_title: String::new(),
+            new_content: String::new(),
+            new_tags: String::new(),
+            new_metadata: HashMap::new(),
+            metadata_key_buffer: String::new(),
+            metadata_buffer: String::new(),
+            edit_id: None,
+            edit_buffers: HashMap::new(),
+            search_query: String::new(),
+            active_tag_filters: HashSet::new(),
+            tag_filter_mode: TagFilterMode::Any,
+            save_error: None,
+            load_error,
         }
     }
 
-    fn save_notes(&self) {
-        let data = serde_json::to_string(&self.notes).expect("Fehler beim serialisieren!");
-        fs::write("notes.json", data).expect("Fehler beim Schreiben!");
+    fn save_notes(&mut self) {
+        let result = match &self.password {
+            Some(password) => storage::save_encrypted(&self.notes, password),
+            None => storage::save_plaintext(&self.notes),
+        };
+        self.save_error = result.err();
     }
 
     fn add_note(&mut self) {
@@ -60,61 +136,270 @@ impl NotebookApp {
         .filter(|s| !s.is_empty())
         .collect();
 
-        let new_note = Note::new(self.new_title.clone(), self.new_content.clone(), tags);
+        let new_note = Note::new(
+            self.new_title.clone(),
+            self.new_content.clone(),
+            tags,
+            self.new_metadata.clone(),
+        );
         self.notes.push(new_note);
         self.new_title.clear();
         self.new_content.clear();
         self.new_tags.clear();
+        self.new_metadata.clear();
         self.save_notes();
     }
 
-    fn delete_note_by_index(&mut self, index: usize) {
-        if index < self.notes.len() {
-            self.notes.remove(index);
-            self.save_notes();
-        }
+    fn delete_note(&mut self, id: Uuid) {
+        self.notes.retain(|note| note.id != id);
+        self.edit_buffers.remove(&id);
+        self.save_notes();
     }
 
-    fn update_note_by_index(&mut self, index: usize, new_content: String) {
-        if let Some(note) = self.notes.get_mut(index) {
+    fn update_note(&mut self, id: Uuid, new_content: String) {
+        if let Some(note) = self.notes.iter_mut().find(|note| note.id == id) {
             note.update(new_content);
             self.save_notes();
         }
     }
+
+    fn add_metadata_field(&mut self) {
+        if let Some((key, value)) = trimmed_metadata_entry(&self.metadata_key_buffer, &self.metadata_buffer) {
+            self.new_metadata.insert(key, value);
+            self.metadata_key_buffer.clear();
+            self.metadata_buffer.clear();
+        }
+    }
+
+    fn visible_note_ids(&self) -> Vec<Uuid> {
+        let mut notes: Vec<&Note> = self.notes.iter().filter(|note| self.matches_filters(note)).collect();
+        notes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        notes.into_iter().map(|note| note.id).collect()
+    }
+
+    fn matches_filters(&self, note: &Note) -> bool {
+        let query = self.search_query.trim().to_lowercase();
+        if !query.is_empty()
+            && !note.title.to_lowercase().contains(&query)
+            && !note.content.to_lowercase().contains(&query)
+        {
+            return false;
+        }
+
+        if self.active_tag_filters.is_empty() {
+            return true;
+        }
+
+        match self.tag_filter_mode {
+            TagFilterMode::Any => self.active_tag_filters.iter().any(|tag| note.tags.contains(tag)),
+            TagFilterMode::All => self.active_tag_filters.iter().all(|tag| note.tags.contains(tag)),
+        }
+    }
+
+    fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.notes.iter().flat_map(|note| note.tags.iter().cloned()).collect::<HashSet<_>>().into_iter().collect();
+        tags.sort();
+        tags
+    }
+
+    fn show_password_prompt(&mut self, ctx: &eframe::egui::Context) -> bool {
+        let Some(input) = &mut self.password_input else { return false };
+        // (notes, password, needs_save) — needs_save is only true when content must be
+        // (re-)encrypted for the first time; unlocking or staying unencrypted writes nothing new.
+        let mut unlocked: Option<(Vec<Note>, Option<String>, bool)> = None;
+
+        eframe::egui::CentralPanel::default().show(ctx, |ui| {
+            match &input.mode {
+                PasswordPromptMode::Unlock => {
+                    ui.heading("Notizbuch entsperren");
+                    ui.label("Dieses Notizbuch ist verschlüsselt. Bitte Passwort eingeben:");
+                    ui.add(eframe::egui::TextEdit::singleline(&mut input.password).password(true));
+
+                    if ui.button("Entsperren").clicked() {
+                        match storage::load_encrypted(&input.password) {
+                            Ok(notes) => unlocked = Some((notes, Some(input.password.clone()), false)),
+                            Err(err) => input.error = Some(err),
+                        }
+                    }
+                }
+                PasswordPromptMode::CreatePassword { plaintext_notes } => {
+                    if plaintext_notes.is_empty() {
+                        ui.heading("Master-Passwort festlegen");
+                        ui.label("Lege ein Passwort fest, mit dem notes.json verschlüsselt wird:");
+                    } else {
+                        ui.heading("Unverschlüsseltes Notizbuch gefunden");
+                        ui.label("Lege ein Passwort fest, um die bestehenden Notizen jetzt zu verschlüsseln:");
+                    }
+                    ui.add(eframe::egui::TextEdit::singleline(&mut input.password).password(true));
+                    ui.label("Passwort bestätigen:");
+                    ui.add(eframe::egui::TextEdit::singleline(&mut input.confirm_password).password(true));
+
+                    if ui.button("Festlegen").clicked() {
+                        if input.password.is_empty() {
+                            input.error = Some("Passwort darf nicht leer sein.".to_string());
+                        } else if input.password != input.confirm_password {
+                            input.error = Some("Passwörter stimmen nicht überein.".to_string());
+                        } else {
+                            unlocked = Some((plaintext_notes.clone(), Some(input.password.clone()), true));
+                        }
+                    }
+
+                    if ui.button("Ohne Verschlüsselung fortfahren").clicked() {
+                        unlocked = Some((plaintext_notes.clone(), None, false));
+                    }
+                }
+            }
+
+            if let Some(error) = &input.error {
+                ui.colored_label(eframe::egui::Color32::RED, error);
+            }
+        });
+
+        if let Some((notes, password, needs_save)) = unlocked {
+            self.notes = notes;
+            self.password = password;
+            self.password_input = None;
+            if needs_save {
+                self.save_notes();
+            }
+            return false;
+        }
+
+        true
+    }
 }
 
 impl eframe::App for NotebookApp {
     fn update(&mut self, ctx: &eframe::egui::Context, _frame: &mut eframe::Frame) {
+        if let Some(error) = &self.load_error {
+            eframe::egui::CentralPanel::default().show(ctx, |ui| {
+                ui.heading("Notizbuch konnte nicht geladen werden");
+                ui.colored_label(eframe::egui::Color32::RED, error);
+                ui.label("Bitte das Problem beheben und die Anwendung neu starten.");
+            });
+            return;
+        }
+
+        if self.show_password_prompt(ctx) {
+            return;
+        }
+
         eframe::egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Notizbuch");
 
+            if let Some(error) = &self.save_error {
+                ui.colored_label(eframe::egui::Color32::RED, error);
+            }
+
             ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Suche:");
+                    ui.text_edit_singleline(&mut self.search_query);
+                });
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Tags:");
+                    for tag in self.all_tags() {
+                        let active = self.active_tag_filters.contains(&tag);
+                        if ui.selectable_label(active, &tag).clicked() {
+                            if active {
+                                self.active_tag_filters.remove(&tag);
+                            } else {
+                                self.active_tag_filters.insert(tag);
+                            }
+                        }
+                    }
+
+                    if !self.active_tag_filters.is_empty() {
+                        let mode_label = match self.tag_filter_mode {
+                            TagFilterMode::Any => "Modus: Beliebiger Tag (ODER)",
+                            TagFilterMode::All => "Modus: Alle Tags (UND)",
+                        };
+                        if ui.button(mode_label).clicked() {
+                            self.tag_filter_mode = match self.tag_filter_mode {
+                                TagFilterMode::Any => TagFilterMode::All,
+                                TagFilterMode::All => TagFilterMode::Any,
+                            };
+                        }
+                    }
+                });
+
+                ui.separator();
+
                 eframe::egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
-                    let mut indices_to_delete = Vec::new();
+                    let mut id_to_delete = None;
+                    let mut id_to_edit = None;
+                    let mut content_to_save = None;
+
+                    for id in self.visible_note_ids() {
+                        let Some(note) = self.notes.iter().find(|note| note.id == id) else {
+                            continue;
+                        };
 
-                    for (index, note) in self.notes.iter().enumerate() {
                         ui.group(|ui| {
                             ui.label(format!("Titel: {}", note.title));
-                            ui.label(format!("Inhalt: {}", note.content));
+
+                            let editing = self.edit_buffers.contains_key(&note.id);
+                            ui.horizontal(|ui| {
+                                ui.label("Inhalt:");
+                                if ui.button(if editing { "Vorschau" } else { "Text bearbeiten" }).clicked() {
+                                    if editing {
+                                        self.edit_buffers.remove(&note.id);
+                                    } else {
+                                        self.edit_buffers.insert(note.id, note.content.clone());
+                                    }
+                                }
+                            });
+                            if let Some(buffer) = self.edit_buffers.get_mut(&note.id) {
+                                ui.text_edit_multiline(buffer);
+                                if ui.button("Speichern").clicked() {
+                                    content_to_save = Some((note.id, buffer.clone()));
+                                }
+                            } else {
+                                markdown::render(ui, &note.content);
+                            }
+
                             ui.label(format!("Erstellt: {}", note.created_at));
                             ui.label(format!("Letzte Änderung: {}", note.updated_at));
                             ui.label(format!("Tags: {:?}", note.tags));
-                            
+
+                            if !note.metadata.is_empty() {
+                                ui.label("Metadaten:");
+                                for (key, value) in &note.metadata {
+                                    ui.label(format!("  {key}: {value}"));
+                                }
+                            }
+
                             if ui.button("Bearbeiten").clicked() {
-                                self.new_title = note.title.clone();
-                                self.new_content = note.content.clone();
-                                self.new_tags = note.tags.join(", ");
-                                self.edit_index = Some(index);
+                                id_to_edit = Some(note.id);
                             }
 
                             if ui.button("Löschen").clicked() {
-                                indices_to_delete.push(index);
+                                id_to_delete = Some(note.id);
                             }
                         });
                     }
 
-                    for &index in indices_to_delete.iter().rev() {
-                        self.delete_note_by_index(index);
+                    if let Some(id) = id_to_edit {
+                        if let Some(note) = self.notes.iter().find(|note| note.id == id) {
+                            self.new_title = note.title.clone();
+                            self.new_content = note.content.clone();
+                            self.new_tags = note.tags.join(", ");
+                            self.new_metadata = note.metadata.clone();
+                            self.edit_id = Some(id);
+                        }
+                    }
+
+                    if let Some(id) = id_to_delete {
+                        self.delete_note(id);
+                    }
+
+                    if let Some((id, content)) = content_to_save {
+                        if self.edit_id == Some(id) {
+                            self.new_content = content.clone();
+                        }
+                        self.update_note(id, content);
+                        self.edit_buffers.remove(&id);
                     }
                 });
 
@@ -135,7 +420,29 @@ impl eframe::App for NotebookApp {
                     ui.text_edit_singleline(&mut self.new_tags);
                 });
 
-                if let Some(edit_index) = self.edit_index {
+                ui.label("Metadaten:");
+                let mut metadata_to_remove = None;
+                for (key, value) in self.new_metadata.iter() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{key}: {value}"));
+                        if ui.button("Entfernen").clicked() {
+                            metadata_to_remove = Some(key.clone());
+                        }
+                    });
+                }
+                if let Some(key) = metadata_to_remove {
+                    self.new_metadata.remove(&key);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.metadata_key_buffer);
+                    ui.text_edit_singleline(&mut self.metadata_buffer);
+                    if ui.button("Feld hinzufügen").clicked() {
+                        self.add_metadata_field();
+                    }
+                });
+
+                if let Some(edit_id) = self.edit_id {
                     if ui.button("Änderungen speichern").clicked() {
                         if !self.new_content.is_empty() {
                             let tags: Vec<String> = self.new_tags.split(',')
@@ -143,15 +450,18 @@ impl eframe::App for NotebookApp {
                                 .filter(|s| !s.is_empty())
                                 .collect();
 
-                            self.notes[edit_index].title = self.new_title.clone();
-                            self.notes[edit_index].update(self.new_content.clone());
-                            self.notes[edit_index].tags = tags;
-                            self.save_notes();
+                            if let Some(note) = self.notes.iter_mut().find(|note| note.id == edit_id) {
+                                note.title = self.new_title.clone();
+                                note.tags = tags;
+                                note.metadata = self.new_metadata.clone();
+                            }
+                            self.update_note(edit_id, self.new_content.clone());
 
                             self.new_title.clear();
                             self.new_content.clear();
                             self.new_tags.clear();
-                            self.edit_index = None;
+                            self.new_metadata.clear();
+                            self.edit_id = None;
                         }
                     }
                 } else if ui.button("Neue Notiz hinzufügen").clicked() {
@@ -164,14 +474,137 @@ impl eframe::App for NotebookApp {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_app(notes: Vec<Note>) -> NotebookApp {
+        NotebookApp {
+            notes,
+            password: None,
+            password_input: None,
+            new_title: String::new(),
+            new_content: String::new(),
+            new_tags: String::new(),
+            new_metadata: HashMap::new(),
+            metadata_key_buffer: String::new(),
+            metadata_buffer: String::new(),
+            edit_id: None,
+            edit_buffers: HashMap::new(),
+            search_query: String::new(),
+            active_tag_filters: HashSet::new(),
+            tag_filter_mode: TagFilterMode::Any,
+            save_error: None,
+            load_error: None,
+        }
+    }
+
+    fn note_with_tags(title: &str, tags: &[&str]) -> Note {
+        Note::new(
+            title.to_string(),
+            "Inhalt".to_string(),
+            tags.iter().map(|t| t.to_string()).collect(),
+            HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn visible_note_ids_are_sorted_newest_first() {
+        let mut older = note_with_tags("Alt", &[]);
+        older.created_at = Utc::now() - chrono::Duration::hours(1);
+        let newer = note_with_tags("Neu", &[]);
+        let newer_id = newer.id;
+        let older_id = older.id;
+
+        let app = test_app(vec![older, newer]);
+
+        assert_eq!(app.visible_note_ids(), vec![newer_id, older_id]);
+    }
+
+    #[test]
+    fn matches_filters_any_mode_matches_on_one_shared_tag() {
+        let mut app = test_app(vec![note_with_tags("Notiz", &["rust", "gui"])]);
+        app.active_tag_filters.insert("rust".to_string());
+        app.active_tag_filters.insert("backend".to_string());
+        app.tag_filter_mode = TagFilterMode::Any;
+
+        assert!(app.matches_filters(&app.notes[0]));
+    }
+
+    #[test]
+    fn matches_filters_all_mode_requires_every_tag() {
+        let mut app = test_app(vec![note_with_tags("Notiz", &["rust", "gui"])]);
+        app.active_tag_filters.insert("rust".to_string());
+        app.active_tag_filters.insert("backend".to_string());
+        app.tag_filter_mode = TagFilterMode::All;
+
+        assert!(!app.matches_filters(&app.notes[0]));
+
+        app.active_tag_filters.remove("backend");
+        assert!(app.matches_filters(&app.notes[0]));
+    }
+
+    #[test]
+    fn matches_filters_substring_search_is_case_insensitive() {
+        let mut app = test_app(vec![note_with_tags("Einkaufsliste", &[])]);
+        app.search_query = "EINKAUF".to_string();
+
+        assert!(app.matches_filters(&app.notes[0]));
+
+        app.search_query = "urlaub".to_string();
+        assert!(!app.matches_filters(&app.notes[0]));
+    }
+
+    #[test]
+    fn all_tags_are_deduplicated_and_sorted() {
+        let app = test_app(vec![
+            note_with_tags("Eins", &["rust", "gui"]),
+            note_with_tags("Zwei", &["backend", "rust"]),
+        ]);
+
+        assert_eq!(app.all_tags(), vec!["backend", "gui", "rust"]);
+    }
+
+    #[test]
+    fn trimmed_metadata_entry_rejects_blank_key() {
+        assert_eq!(trimmed_metadata_entry("   ", "value"), None);
+    }
+
+    #[test]
+    fn trimmed_metadata_entry_trims_whitespace() {
+        assert_eq!(
+            trimmed_metadata_entry("  author ", " Jane Doe "),
+            Some(("author".to_string(), "Jane Doe".to_string()))
+        );
+    }
+
+    #[test]
+    fn add_metadata_field_inserts_and_clears_buffers() {
+        let mut app = test_app(Vec::new());
+        app.metadata_key_buffer = "source".to_string();
+        app.metadata_buffer = "https://example.com".to_string();
+
+        app.add_metadata_field();
+
+        assert_eq!(app.new_metadata.get("source"), Some(&"https://example.com".to_string()));
+        assert!(app.metadata_key_buffer.is_empty());
+        assert!(app.metadata_buffer.is_empty());
+    }
+
+    #[test]
+    fn add_metadata_field_ignores_blank_key() {
+        let mut app = test_app(Vec::new());
+        app.metadata_key_buffer = "   ".to_string();
+        app.metadata_buffer = "value".to_string();
+
+        app.add_metadata_field();
+
+        assert!(app.new_metadata.is_empty());
+    }
+}
+
 fn main() {
-    let app = NotebookApp {
-        notes: NotebookApp::load_notes(),
-        new_title: String::new(),
-        new_content: String::new(),
-        new_tags: String::new(),
-        edit_index: None,
-    };
+    let app = NotebookApp::new();
 
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(
@@ -179,4 +612,4 @@ fn main() {
         native_options,
         Box::new(|_cc| Ok(Box::new(app))),
     ).expect("Fehler beim Starten!");
-}
\ No newline at end of file
+}
@@ -0,0 +1,249 @@
+use eframe::egui::{RichText, Ui};
+
+struct Span {
+    text: String,
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    link: Option<String>,
+}
+
+pub fn render(ui: &mut Ui, content: &str) {
+    for line in content.lines() {
+        render_block(ui, line);
+    }
+}
+
+fn render_block(ui: &mut Ui, line: &str) {
+    let trimmed = line.trim_end();
+
+    if trimmed.trim() == "---" {
+        ui.separator();
+        return;
+    }
+
+    if trimmed.trim().is_empty() {
+        ui.add_space(4.0);
+        return;
+    }
+
+    if let Some(rest) = trimmed.strip_prefix("#### ") {
+        render_heading(ui, rest, 14.0);
+    } else if let Some(rest) = trimmed.strip_prefix("### ") {
+        render_heading(ui, rest, 16.0);
+    } else if let Some(rest) = trimmed.strip_prefix("## ") {
+        render_heading(ui, rest, 19.0);
+    } else if let Some(rest) = trimmed.strip_prefix("# ") {
+        render_heading(ui, rest, 24.0);
+    } else if let Some(rest) = trimmed.strip_prefix("> ") {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new("▌").weak());
+            render_spans(ui, &parse_inline(rest), None);
+        });
+    } else if let Some(rest) = strip_task_prefix(trimmed, false) {
+        ui.horizontal_wrapped(|ui| {
+            let mut checked = false;
+            ui.add_enabled(false, eframe::egui::Checkbox::new(&mut checked, ""));
+            render_spans(ui, &parse_inline(rest), None);
+        });
+    } else if let Some(rest) = strip_task_prefix(trimmed, true) {
+        ui.horizontal_wrapped(|ui| {
+            let mut checked = true;
+            ui.add_enabled(false, eframe::egui::Checkbox::new(&mut checked, ""));
+            render_spans(ui, &parse_inline(rest), None);
+        });
+    } else if let Some(rest) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("•");
+            render_spans(ui, &parse_inline(rest), None);
+        });
+    } else {
+        ui.horizontal_wrapped(|ui| {
+            render_spans(ui, &parse_inline(trimmed), None);
+        });
+    }
+}
+
+fn strip_task_prefix(line: &str, checked: bool) -> Option<&str> {
+    let marker = if checked { "[x] " } else { "[ ] " };
+    line.strip_prefix(&format!("- {marker}"))
+        .or_else(|| line.strip_prefix(&format!("* {marker}")))
+}
+
+fn render_heading(ui: &mut Ui, text: &str, size: f32) {
+    ui.horizontal_wrapped(|ui| {
+        render_spans(ui, &parse_inline(text), Some(size));
+    });
+}
+
+fn render_spans(ui: &mut Ui, spans: &[Span], size: Option<f32>) {
+    for span in spans {
+        let mut rich = RichText::new(&span.text);
+        if let Some(size) = size {
+            rich = rich.size(size).strong();
+        }
+        if span.bold {
+            rich = rich.strong();
+        }
+        if span.italic {
+            rich = rich.italics();
+        }
+        if span.strike {
+            rich = rich.strikethrough();
+        }
+        if let Some(url) = &span.link {
+            ui.hyperlink_to(rich, url);
+        } else {
+            ui.label(rich);
+        }
+    }
+}
+
+fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut buf = String::new();
+    let (mut bold, mut italic, mut strike) = (false, false, false);
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    macro_rules! flush {
+        () => {
+            if !buf.is_empty() {
+                spans.push(Span {
+                    text: std::mem::take(&mut buf),
+                    bold,
+                    italic,
+                    strike,
+                    link: None,
+                });
+            }
+        };
+    }
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '*' && chars.get(i + 1) == Some(&'*') {
+            flush!();
+            bold = !bold;
+            i += 2;
+            continue;
+        }
+
+        if c == '_' {
+            if italic && can_close_underscore(&chars, i) {
+                flush!();
+                italic = false;
+                i += 1;
+                continue;
+            }
+            if !italic && can_open_underscore(&chars, i) && find_closing_underscore(&chars, i).is_some() {
+                flush!();
+                italic = true;
+                i += 1;
+                continue;
+            }
+            buf.push('_');
+            i += 1;
+            continue;
+        }
+
+        if c == '~' {
+            flush!();
+            strike = !strike;
+            i += 1;
+            continue;
+        }
+
+        if c == '[' {
+            if let Some(link) = parse_link(&chars[i..]) {
+                flush!();
+                spans.push(Span {
+                    text: link.label,
+                    bold,
+                    italic,
+                    strike,
+                    link: Some(link.url),
+                });
+                i += link.consumed;
+                continue;
+            }
+        }
+
+        buf.push(c);
+        i += 1;
+    }
+
+    flush!();
+    spans
+}
+
+fn find_closing_underscore(chars: &[char], open: usize) -> Option<usize> {
+    let mut j = open + 1;
+    while j < chars.len() {
+        if chars[j] == '_' && can_close_underscore(chars, j) {
+            return Some(j);
+        }
+        j += 1;
+    }
+    None
+}
+
+fn can_open_underscore(chars: &[char], i: usize) -> bool {
+    let left_boundary = i == 0 || !is_word_char(chars[i - 1]);
+    let right_non_space = chars.get(i + 1).map_or(false, |c| !c.is_whitespace());
+    left_boundary && right_non_space
+}
+
+fn can_close_underscore(chars: &[char], i: usize) -> bool {
+    let left_non_space = i > 0 && !chars[i - 1].is_whitespace();
+    let right_boundary = chars.get(i + 1).map_or(true, |c| !is_word_char(*c));
+    left_non_space && right_boundary
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+struct ParsedLink {
+    label: String,
+    url: String,
+    consumed: usize,
+}
+
+fn parse_link(chars: &[char]) -> Option<ParsedLink> {
+    let close_label = chars.iter().position(|&c| c == ']')?;
+    if chars.get(close_label + 1) != Some(&'(') {
+        return None;
+    }
+    let url_start = close_label + 2;
+    let close_url = chars[url_start..].iter().position(|&c| c == ')')?;
+
+    Some(ParsedLink {
+        label: chars[1..close_label].iter().collect(),
+        url: chars[url_start..url_start + close_url].iter().collect(),
+        consumed: url_start + close_url + 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_text(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn snake_case_identifiers_with_multiple_underscores_stay_plain() {
+        let spans = parse_inline("metadata_key_buffer");
+        assert_eq!(plain_text(&spans), "metadata_key_buffer");
+        assert!(spans.iter().all(|s| !s.italic));
+    }
+
+    #[test]
+    fn still_renders_italics() {
+        let spans = parse_inline("an _italic_ word");
+        assert!(spans.iter().any(|s| s.italic && s.text == "italic"));
+    }
+}
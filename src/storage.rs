@@ -0,0 +1,183 @@
+use crate::Note;
+use directories::ProjectDirs;
+use pwbox::{sodium::Sodium, Eraser, ErasedPwBox, Suite};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LEGACY_NOTES_PATH: &str = "notes.json";
+
+pub enum ExistingNotes {
+    None,
+    Plaintext(Vec<Note>),
+    Encrypted,
+    Error(String),
+}
+
+fn notes_path() -> Result<PathBuf, String> {
+    let dirs = ProjectDirs::from("", "", "notizbuch")
+        .ok_or_else(|| "Konnte Datenverzeichnis nicht bestimmen.".to_string())?;
+    let data_dir = dirs.data_dir();
+    fs::create_dir_all(data_dir)
+        .map_err(|e| format!("Konnte Datenverzeichnis nicht anlegen: {e}"))?;
+    Ok(data_dir.join("notes.json"))
+}
+
+fn migrate_legacy_notes(legacy_path: &Path, dest_path: &Path) -> Result<Option<String>, String> {
+    if !legacy_path.exists() {
+        return Ok(None);
+    }
+
+    let data = fs::read_to_string(legacy_path)
+        .map_err(|e| format!("Konnte alte notes.json nicht lesen: {e}"))?;
+    fs::write(dest_path, &data).map_err(|e| format!("Konnte notes.json nicht migrieren: {e}"))?;
+    let _ = fs::remove_file(legacy_path);
+    Ok(Some(data))
+}
+
+pub fn inspect() -> ExistingNotes {
+    let path = match notes_path() {
+        Ok(path) => path,
+        Err(e) => return ExistingNotes::Error(e),
+    };
+
+    let data = if path.exists() {
+        match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) => return ExistingNotes::Error(format!("Konnte Datei nicht laden: {e}")),
+        }
+    } else {
+        match migrate_legacy_notes(Path::new(LEGACY_NOTES_PATH), &path) {
+            Ok(Some(data)) => data,
+            Ok(None) => return ExistingNotes::None,
+            Err(e) => return ExistingNotes::Error(e),
+        }
+    };
+
+    match serde_json::from_str::<Vec<Note>>(&data) {
+        Ok(notes) => ExistingNotes::Plaintext(notes),
+        Err(_) => ExistingNotes::Encrypted,
+    }
+}
+
+fn eraser() -> Eraser {
+    let mut eraser = Eraser::new();
+    eraser.add_suite::<Sodium>();
+    eraser
+}
+
+pub fn load_encrypted(password: &str) -> Result<Vec<Note>, String> {
+    let path = notes_path()?;
+    let data = fs::read_to_string(&path).map_err(|e| format!("Konnte Datei nicht laden: {e}"))?;
+    let erased: ErasedPwBox =
+        serde_json::from_str(&data).map_err(|e| format!("Datei ist beschädigt: {e}"))?;
+
+    let pw_box = eraser()
+        .restore(&erased)
+        .map_err(|e| format!("Datei ist beschädigt: {e}"))?;
+    let opened = pw_box
+        .open(password.as_bytes())
+        .map_err(|_| "Falsches Passwort.".to_string())?;
+
+    serde_json::from_slice(&opened).map_err(|e| format!("Konnte JSON nicht lesen: {e}"))
+}
+
+pub fn save_plaintext(notes: &[Note]) -> Result<(), String> {
+    let path = notes_path()?;
+    let data = serde_json::to_string(notes).map_err(|e| format!("Fehler beim Serialisieren: {e}"))?;
+    fs::write(&path, data).map_err(|e| format!("Fehler beim Schreiben: {e}"))
+}
+
+pub fn save_encrypted(notes: &[Note], password: &str) -> Result<(), String> {
+    let path = notes_path()?;
+    let plaintext = serde_json::to_vec(notes).map_err(|e| format!("Fehler beim Serialisieren: {e}"))?;
+    let pw_box = Sodium::build_box(&mut rand::thread_rng())
+        .seal(password.as_bytes(), &plaintext)
+        .map_err(|e| format!("Fehler beim Verschlüsseln: {e}"))?;
+    let erased = eraser()
+        .erase(&pw_box)
+        .map_err(|e| format!("Fehler beim Verschlüsseln: {e}"))?;
+    let data = serde_json::to_string(&erased).map_err(|e| format!("Fehler beim Serialisieren: {e}"))?;
+
+    fs::write(&path, data).map_err(|e| format!("Fehler beim Schreiben: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn seals_and_restores_notes_round_trip() {
+        let notes = vec![Note::new(
+            "Titel".to_string(),
+            "Inhalt".to_string(),
+            vec!["tag".to_string()],
+            HashMap::new(),
+        )];
+        let plaintext = serde_json::to_vec(&notes).unwrap();
+
+        let pw_box = Sodium::build_box(&mut rand::thread_rng())
+            .seal(b"geheim", &plaintext)
+            .unwrap();
+        let data = serde_json::to_string(&eraser().erase(&pw_box).unwrap()).unwrap();
+
+        let erased: ErasedPwBox = serde_json::from_str(&data).unwrap();
+        let opened = eraser().restore(&erased).unwrap().open(b"geheim").unwrap();
+        let restored: Vec<Note> = serde_json::from_slice(&opened).unwrap();
+
+        assert_eq!(restored[0].title, "Titel");
+        assert_eq!(restored[0].content, "Inhalt");
+    }
+
+    #[test]
+    fn wrong_password_fails_to_open() {
+        let pw_box = Sodium::build_box(&mut rand::thread_rng())
+            .seal(b"richtig", b"geheime notizen")
+            .unwrap();
+        let data = serde_json::to_string(&eraser().erase(&pw_box).unwrap()).unwrap();
+
+        let erased: ErasedPwBox = serde_json::from_str(&data).unwrap();
+        let pw_box = eraser().restore(&erased).unwrap();
+        assert!(pw_box.open(b"falsch").is_err());
+    }
+
+    #[test]
+    fn migrates_legacy_plaintext_notes() {
+        let pid = std::process::id();
+        let legacy = std::env::temp_dir().join(format!("notizbuch-test-legacy-{pid}.json"));
+        let dest = std::env::temp_dir().join(format!("notizbuch-test-dest-{pid}.json"));
+        let _ = fs::remove_file(&legacy);
+        let _ = fs::remove_file(&dest);
+
+        let notes = vec![Note::new("Alt".to_string(), "Alter Inhalt".to_string(), Vec::new(), HashMap::new())];
+        let data = serde_json::to_string(&notes).unwrap();
+        fs::write(&legacy, &data).unwrap();
+
+        let migrated = migrate_legacy_notes(&legacy, &dest).unwrap();
+
+        assert_eq!(migrated.as_deref(), Some(data.as_str()));
+        assert_eq!(fs::read_to_string(&dest).unwrap(), data);
+        assert!(!legacy.exists());
+
+        let _ = fs::remove_file(&legacy);
+        let _ = fs::remove_file(&dest);
+    }
+
+    #[test]
+    fn migration_failure_is_distinct_from_no_legacy_file() {
+        let pid = std::process::id();
+        let legacy = std::env::temp_dir().join(format!("notizbuch-test-nolegacy-{pid}.json"));
+        let _ = fs::remove_file(&legacy);
+
+        assert_eq!(migrate_legacy_notes(&legacy, &legacy).unwrap(), None);
+
+        fs::write(&legacy, "{}").unwrap();
+        let unwritable_dest = std::env::temp_dir()
+            .join(format!("notizbuch-test-missing-dir-{pid}"))
+            .join("notes.json");
+
+        assert!(migrate_legacy_notes(&legacy, &unwritable_dest).is_err());
+
+        let _ = fs::remove_file(&legacy);
+    }
+}